@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// Type alias for `Result`s using [`OpenApiError`] as the error type.
+pub type Result<T> = std::result::Result<T, OpenApiError>;
+
+/// The error type returned by the various generation and merging steps in this crate.
+#[derive(Debug)]
+pub enum OpenApiError {
+    /// A security scheme was registered twice under the same name with conflicting definitions.
+    SecuritySchemeConflict {
+        /// Name the security scheme was registered under.
+        name: String,
+    },
+    /// Two modules mounted a route at the same path, and `OnConflict::Error` was in effect.
+    PathConflict {
+        /// The path both modules tried to register.
+        path: String,
+        /// The mount path of the module whose spec was being merged in when the collision was
+        /// detected.
+        module: String,
+    },
+    /// A generic error message, used for cases that don't warrant their own variant.
+    Other(String),
+}
+
+impl fmt::Display for OpenApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenApiError::SecuritySchemeConflict { name } => write!(
+                f,
+                "security scheme `{}` was registered twice with different definitions",
+                name
+            ),
+            OpenApiError::PathConflict { path, module } => write!(
+                f,
+                "path `{}` is already defined by another module (collided while merging `{}`)",
+                path, module
+            ),
+            OpenApiError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for OpenApiError {}
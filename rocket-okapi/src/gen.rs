@@ -0,0 +1,150 @@
+//! Contains the [`Generator`] struct, which collects the shared `Components` (such as security
+//! schemes) of the documentation for a set of endpoints.
+
+use crate::request::RequestHeaderInput;
+use crate::settings::OpenApiSettings;
+use crate::Result;
+use revolt_okapi::openapi3::{Components, Operation};
+
+/// Used to generate and keep track of the OpenAPI documentation for a set of endpoints.
+///
+/// A `Generator` accumulates shared state (such as `components`) across every call made on it,
+/// e.g. every [`Generator::add_request_header_input`] for the routes it documents.
+pub struct Generator {
+    settings: OpenApiSettings,
+    components: Components,
+}
+
+impl Generator {
+    /// Create a new `Generator` using the given settings.
+    pub fn new(settings: OpenApiSettings) -> Self {
+        Generator {
+            settings,
+            components: Components::default(),
+        }
+    }
+
+    /// The settings this generator was created with.
+    pub fn settings(&self) -> &OpenApiSettings {
+        &self.settings
+    }
+
+    /// The `Components` object accumulated so far, including any registered security schemes.
+    pub fn components(&self) -> &Components {
+        &self.components
+    }
+
+    /// Consume the generator, returning the accumulated `Components`.
+    pub fn into_components(self) -> Components {
+        self.components
+    }
+
+    /// Folds the [`RequestHeaderInput`] returned by a request guard's `OpenApiFromRequest` impl
+    /// into the given `Operation`.
+    ///
+    /// - `RequestHeaderInput::None` is a no-op.
+    /// - `RequestHeaderInput::Security(name, scheme, requirement)` registers `scheme` in
+    ///   `components.security_schemes` (deduplicated by name) and adds `requirement` to the
+    ///   operation's `security` list, so the endpoint renders a lock icon and documented auth in
+    ///   Swagger/RapiDoc without manual spec editing.
+    ///
+    /// Returns `OpenApiError::SecuritySchemeConflict` if `name` was already registered with a
+    /// different `SecurityScheme` by another request guard.
+    pub fn add_request_header_input(
+        &mut self,
+        operation: &mut Operation,
+        input: RequestHeaderInput,
+    ) -> Result<()> {
+        match input {
+            RequestHeaderInput::None => {}
+            RequestHeaderInput::Security(name, scheme, requirement) => {
+                match self.components.security_schemes.get(&name) {
+                    Some(existing) if existing != &scheme => {
+                        return Err(crate::OpenApiError::SecuritySchemeConflict { name })
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.components.security_schemes.insert(name, scheme);
+                    }
+                }
+                operation.security.get_or_insert_with(Vec::new).push(requirement);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revolt_okapi::openapi3::{SecurityRequirement, SecurityScheme, SecuritySchemeData};
+
+    fn api_key_scheme(name: &str) -> SecurityScheme {
+        SecurityScheme {
+            description: None,
+            data: SecuritySchemeData::ApiKey {
+                name: name.to_owned(),
+                location: "header".to_owned(),
+            },
+            extensions: Default::default(),
+        }
+    }
+
+    #[test]
+    fn dedupes_identical_scheme_by_name() {
+        let mut gen = Generator::new(OpenApiSettings::new());
+        let mut operation = Operation::default();
+        gen.add_request_header_input(
+            &mut operation,
+            RequestHeaderInput::Security(
+                "ApiKey".to_owned(),
+                api_key_scheme("X-API-Key"),
+                SecurityRequirement::default(),
+            ),
+        )
+        .unwrap();
+        gen.add_request_header_input(
+            &mut operation,
+            RequestHeaderInput::Security(
+                "ApiKey".to_owned(),
+                api_key_scheme("X-API-Key"),
+                SecurityRequirement::default(),
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(gen.components().security_schemes.len(), 1);
+        assert_eq!(operation.security.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_conflicting_scheme_under_same_name() {
+        let mut gen = Generator::new(OpenApiSettings::new());
+        let mut operation = Operation::default();
+        gen.add_request_header_input(
+            &mut operation,
+            RequestHeaderInput::Security(
+                "ApiKey".to_owned(),
+                api_key_scheme("X-API-Key"),
+                SecurityRequirement::default(),
+            ),
+        )
+        .unwrap();
+
+        let err = gen
+            .add_request_header_input(
+                &mut operation,
+                RequestHeaderInput::Security(
+                    "ApiKey".to_owned(),
+                    api_key_scheme("Authorization"),
+                    SecurityRequirement::default(),
+                ),
+            )
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            crate::OpenApiError::SecuritySchemeConflict { name } if name == "ApiKey"
+        ));
+    }
+}
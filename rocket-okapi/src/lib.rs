@@ -60,6 +60,8 @@
 //!
 //! This crate exposes a few macros that can be used to generate and serve routes and OpenApi objects.
 //! - `mount_endpoints_and_merged_docs!{...}`: Mount endpoints and mount merged OpenAPI documentation.
+//! - `mount_endpoints_and_return_merged_docs!{...}`: Same as above, but also returns the merged
+//! `OpenApi` object so it can be post-processed or persisted (see [`util::write_openapi_spec`]).
 //! - `openapi_get_routes![...]`: To generate and add the `openapi.json` route.
 //! - `openapi_get_routes_spec![...]`: To generate and return a list of routes and the openapi spec.
 //! - `openapi_get_spec![...]`: To generate and return the openapi spec.
@@ -79,6 +81,9 @@ pub mod gen;
 /// Contains several `Rocket` `Handler`s, which are used for serving the json files and the swagger
 /// interface.
 pub mod handlers;
+/// Contains conflict-aware merging of the per-module specs used by
+/// `mount_endpoints_and_merged_docs!`.
+pub mod merge;
 /// Contains the functions and structs required to display the RapiDoc UI.
 #[cfg(feature = "rapidoc")]
 pub mod rapidoc;
@@ -139,6 +144,12 @@ pub fn get_openapi_route(
 ///   - `=>`: divider
 ///   - route_and_docs: `(Vec<rocket::Route>, OpenApi)`
 ///
+/// Merging is conflict-aware: colliding `components.schemas` entries are renamed automatically
+/// (suffixed with the owning module's path) when the schemas differ, and colliding `paths` keys
+/// are resolved according to `openapi_settings.on_conflict` (see [`settings::OnConflict`]). If
+/// you need to handle a merge failure without panicking, call [`merge::merge_with_strategy`]
+/// directly instead of this macro.
+///
 /// Example:
 /// ```rust,ignore
 /// let custom_route_spec = (vec![], custom_spec());
@@ -163,7 +174,7 @@ macro_rules! mount_endpoints_and_merged_docs {
             openapi_list.push(($path, openapi));
         })*
         // Combine all OpenApi documentation into one struct.
-        let openapi_docs = match revolt_rocket_okapi::revolt_okapi::merge::marge_spec_list(&openapi_list){
+        let openapi_docs = match revolt_rocket_okapi::merge::merge_with_strategy(openapi_list, $openapi_settings.on_conflict) {
             Ok(docs) => docs,
             Err(err) => panic!("Could not merge OpenAPI spec: {}", err),
         };
@@ -178,6 +189,59 @@ macro_rules! mount_endpoints_and_merged_docs {
     }};
 }
 
+/// Mount endpoints, mount merged OpenAPI documentation, and return the merged `OpenApi` object.
+///
+/// This works like [`mount_endpoints_and_merged_docs!`], except that it hands back the merged
+/// `revolt_okapi::openapi3::OpenApi` document instead of discarding it. This is handy when
+/// you want to tweak the spec before/after it is served (e.g. setting `servers`, global
+/// `security`, or contact/license info), or when you want to write it to disk with
+/// [`util::write_openapi_spec`] for CI diffing.
+///
+/// Unlike `mount_endpoints_and_merged_docs!`, a merge collision does not panic here: the macro
+/// evaluates to `Result<(Rocket<Build>, OpenApi), OpenApiError>`, so the caller can match on it
+/// and log which path collided instead of the process aborting. (Colliding schemas never produce
+/// an error: they are renamed.)
+///
+/// The macro expects the same arguments as `mount_endpoints_and_merged_docs!`.
+///
+/// Example:
+/// ```rust,ignore
+/// let (building_rocket, merged_spec) = mount_endpoints_and_return_merged_docs! {
+///     building_rocket, "/v1".to_owned(), openapi_settings,
+///     "/post" => post::get_routes_and_docs(),
+///     "/message" => message::get_routes_and_docs(),
+/// }.unwrap_or_else(|err| panic!("Could not merge OpenAPI spec: {}", err));
+/// revolt_rocket_okapi::util::write_openapi_spec(&merged_spec, "openapi.json")
+///     .expect("could not write openapi.json");
+/// ```
+#[macro_export]
+macro_rules! mount_endpoints_and_return_merged_docs {
+    ($rocket_builder:ident, $base_path:expr, $openapi_settings:ident,
+     $($path:expr => $route_and_docs:expr),* $(,)*) => {{
+        let base_path = $base_path.to_string();
+        assert!(base_path == "/" || !base_path.ends_with("/"), "`base_path` should not end with an `/`.");
+        let mut openapi_list: Vec<(_, revolt_rocket_okapi::revolt_okapi::openapi3::OpenApi)> = Vec::new();
+        $({
+            let (routes, openapi) = $route_and_docs;
+            $rocket_builder = $rocket_builder.mount(format!("{}{}", base_path, $path), routes);
+            openapi_list.push(($path, openapi));
+        })*
+        // Combine all OpenApi documentation into one struct.
+        revolt_rocket_okapi::merge::merge_with_strategy(openapi_list, $openapi_settings.on_conflict)
+            .map(|openapi_docs| {
+                // Add OpenApi route
+                $rocket_builder = $rocket_builder.mount(
+                    $base_path,
+                    vec![revolt_rocket_okapi::get_openapi_route(
+                        openapi_docs.clone(),
+                        &$openapi_settings,
+                    )],
+                );
+                ($rocket_builder, openapi_docs)
+            })
+    }};
+}
+
 /// A replacement macro for `rocket::routes`. This also takes a optional settings object.
 ///
 /// The key differences are that this macro will add an additional element to the
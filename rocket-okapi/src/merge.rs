@@ -0,0 +1,307 @@
+//! Conflict-aware merging of the per-module `(Vec<Route>, OpenApi)` pairs used by
+//! `mount_endpoints_and_merged_docs!` into a single `OpenApi` document.
+//!
+//! Unlike `revolt_okapi::merge::marge_spec_list`, which fails the whole merge on the first
+//! collision, this:
+//! - automatically renames colliding `components.schemas` entries (and rewrites the `$ref`s that
+//!   point at them), suffixed with the owning module's mount path, when two modules define
+//!   distinct schemas under the same name. Identical schemas sharing a name are merged as-is.
+//! - resolves colliding `paths` keys according to the given [`OnConflict`] strategy, returning a
+//!   structured [`OpenApiError`] instead of panicking when that strategy is `OnConflict::Error`.
+
+use crate::settings::OnConflict;
+use crate::{OpenApiError, Result};
+use revolt_okapi::openapi3::OpenApi;
+use revolt_okapi::Map;
+use serde_json::Value;
+
+/// Merge `openapi_list` into a single `OpenApi` document.
+///
+/// `openapi_list` pairs each module's mount path (anything implementing `ToString`, matching the
+/// `path` argument of `mount_endpoints_and_merged_docs!`) with the `OpenApi` spec it produced; the
+/// mount path is only used to name collisions and to suffix renamed schemas, it is not prepended
+/// to any routes (that is already done by `mount_endpoints_and_merged_docs!` before this is
+/// called).
+pub fn merge_with_strategy<P: ToString>(
+    openapi_list: Vec<(P, OpenApi)>,
+    on_conflict: OnConflict,
+) -> Result<OpenApi> {
+    let mut docs = openapi_list.into_iter();
+    let mut merged = match docs.next() {
+        Some((_, first)) => first,
+        None => return Ok(OpenApi::default()),
+    };
+
+    for (path, mut doc) in docs {
+        let path = path.to_string();
+        if let Some(components) = &doc.components {
+            // Figure out which of this module's schemas collide with an already-merged schema of
+            // the same name but different shape, *before* touching `doc`, so every `$ref` in the
+            // module (including ones between its own schemas) can be rewritten in a single pass.
+            // Note: the renamed name (`{name}_{sanitize(path)}`) is not re-checked against
+            // `merged_schemas`, so it can itself collide with an already-merged schema -- e.g. a
+            // module mounted at `/b` colliding on `User` produces `User_b`, which silently
+            // overwrites a `User_b` that an earlier module defined, or that an earlier module
+            // mounted at a path that also sanitizes to `b` produced by renaming, when the
+            // components are extended in below. This is rare in practice and is accepted rather
+            // than guarded against.
+            let merged_schemas = merged.components.as_ref().map(|c| &c.schemas);
+            let renames: Map<String, String> = components
+                .schemas
+                .iter()
+                .filter_map(|(name, schema)| {
+                    match merged_schemas.and_then(|schemas| schemas.get(name)) {
+                        Some(existing) if existing != schema => {
+                            Some((name.clone(), format!("{}_{}", name, sanitize(&path))))
+                        }
+                        _ => None,
+                    }
+                })
+                .collect();
+            if !renames.is_empty() {
+                rewrite_schema_refs(&mut doc, &renames);
+            }
+        }
+
+        if let Some(components) = doc.components.take() {
+            let merged_components = merged.components.get_or_insert_with(Default::default);
+            for (name, schema) in components.schemas {
+                merged_components.schemas.insert(name, schema);
+            }
+            merged_components
+                .security_schemes
+                .extend(components.security_schemes);
+            merged_components.responses.extend(components.responses);
+            merged_components.parameters.extend(components.parameters);
+            merged_components.examples.extend(components.examples);
+            merged_components
+                .request_bodies
+                .extend(components.request_bodies);
+            merged_components.headers.extend(components.headers);
+            merged_components.links.extend(components.links);
+            merged_components.callbacks.extend(components.callbacks);
+        }
+
+        for (route, item) in doc.paths {
+            if merged.paths.contains_key(&route) {
+                match on_conflict {
+                    OnConflict::Error => {
+                        return Err(OpenApiError::PathConflict {
+                            path: route,
+                            module: path,
+                        })
+                    }
+                    OnConflict::KeepFirst => continue,
+                    OnConflict::KeepLast => {
+                        merged.paths.insert(route, item);
+                    }
+                }
+            } else {
+                merged.paths.insert(route, item);
+            }
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Turn a mount path like `/post/comments` into an identifier-safe suffix like `post_comments`.
+fn sanitize(path: &str) -> String {
+    let sanitized: String = path
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    sanitized.trim_matches('_').to_owned()
+}
+
+/// Rename every key in `renames` (old schema name -> new schema name) throughout `doc`: every
+/// `$ref` string pointing at `#/components/schemas/{old_name}` is rewritten to point at the new
+/// name, and the corresponding key in `doc.components.schemas` itself is renamed to match. All of
+/// `renames` are applied in a single JSON round-trip, regardless of how many schemas collided.
+fn rewrite_schema_refs(doc: &mut OpenApi, renames: &Map<String, String>) {
+    let ref_renames: Map<String, String> = renames
+        .iter()
+        .map(|(old, new)| {
+            (
+                format!("#/components/schemas/{}", old),
+                format!("#/components/schemas/{}", new),
+            )
+        })
+        .collect();
+
+    let mut value = match serde_json::to_value(&doc) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+    replace_ref_strings(&mut value, &ref_renames);
+    if let Some(schemas) = value
+        .get_mut("components")
+        .and_then(|components| components.get_mut("schemas"))
+        .and_then(|schemas| schemas.as_object_mut())
+    {
+        for (old_name, new_name) in renames {
+            if let Some(schema) = schemas.remove(old_name) {
+                schemas.insert(new_name.clone(), schema);
+            }
+        }
+    }
+    if let Ok(rewritten) = serde_json::from_value(value) {
+        *doc = rewritten;
+    }
+}
+
+fn replace_ref_strings(value: &mut Value, ref_renames: &Map<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(new_ref) = ref_renames.get(s.as_str()) {
+                *s = new_ref.clone();
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                replace_ref_strings(item, ref_renames);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                replace_ref_strings(item, ref_renames);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revolt_okapi::openapi3::{Components, Operation, PathItem};
+    use schemars::schema::SchemaObject;
+
+    fn schema_with_format(format: &str) -> SchemaObject {
+        SchemaObject {
+            format: Some(format.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn schema_ref(target: &str) -> SchemaObject {
+        SchemaObject {
+            reference: Some(format!("#/components/schemas/{}", target)),
+            ..Default::default()
+        }
+    }
+
+    fn doc_with_path(route: &str, summary: &str) -> OpenApi {
+        let mut doc = OpenApi::default();
+        doc.paths.insert(
+            route.to_owned(),
+            PathItem {
+                get: Some(Operation {
+                    summary: Some(summary.to_owned()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        doc
+    }
+
+    #[test]
+    fn merges_empty_list_into_default_spec() {
+        let merged = merge_with_strategy(Vec::<(&str, OpenApi)>::new(), OnConflict::Error).unwrap();
+        assert_eq!(merged, OpenApi::default());
+    }
+
+    #[test]
+    fn merges_identical_schema_without_renaming() {
+        let mut doc_a = OpenApi::default();
+        doc_a.components = Some(Components {
+            schemas: vec![("User".to_owned(), schema_with_format("int32"))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        });
+        let mut doc_b = OpenApi::default();
+        doc_b.components = Some(Components {
+            schemas: vec![("User".to_owned(), schema_with_format("int32"))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        });
+
+        let merged =
+            merge_with_strategy(vec![("a", doc_a), ("b", doc_b)], OnConflict::Error).unwrap();
+
+        let schemas = &merged.components.unwrap().schemas;
+        assert_eq!(schemas.len(), 1);
+        assert_eq!(schemas.get("User"), Some(&schema_with_format("int32")));
+    }
+
+    #[test]
+    fn renames_colliding_schema_and_rewrites_sibling_ref() {
+        let mut doc_a = OpenApi::default();
+        doc_a.components = Some(Components {
+            schemas: vec![("User".to_owned(), schema_with_format("int32"))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        });
+
+        let mut doc_b = OpenApi::default();
+        doc_b.components = Some(Components {
+            schemas: vec![
+                ("User".to_owned(), schema_with_format("int64")),
+                ("Comment".to_owned(), schema_ref("User")),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        });
+
+        let merged =
+            merge_with_strategy(vec![("a", doc_a), ("b", doc_b)], OnConflict::Error).unwrap();
+
+        let schemas = merged.components.unwrap().schemas;
+        assert_eq!(schemas.get("User"), Some(&schema_with_format("int32")));
+        assert_eq!(schemas.get("User_b"), Some(&schema_with_format("int64")));
+        assert_eq!(schemas.get("Comment"), Some(&schema_ref("User_b")));
+    }
+
+    #[test]
+    fn on_conflict_error_reports_colliding_path() {
+        let doc_a = doc_with_path("/dup", "first");
+        let doc_b = doc_with_path("/dup", "second");
+
+        let err = merge_with_strategy(vec![("a", doc_a), ("b", doc_b)], OnConflict::Error)
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            OpenApiError::PathConflict { path, module } if path == "/dup" && module == "b"
+        ));
+    }
+
+    #[test]
+    fn on_conflict_keep_first_discards_later_path() {
+        let doc_a = doc_with_path("/dup", "first");
+        let doc_b = doc_with_path("/dup", "second");
+
+        let merged =
+            merge_with_strategy(vec![("a", doc_a), ("b", doc_b)], OnConflict::KeepFirst).unwrap();
+
+        let summary = merged.paths.get("/dup").unwrap().get.as_ref().unwrap().summary.clone();
+        assert_eq!(summary, Some("first".to_owned()));
+    }
+
+    #[test]
+    fn on_conflict_keep_last_overwrites_earlier_path() {
+        let doc_a = doc_with_path("/dup", "first");
+        let doc_b = doc_with_path("/dup", "second");
+
+        let merged =
+            merge_with_strategy(vec![("a", doc_a), ("b", doc_b)], OnConflict::KeepLast).unwrap();
+
+        let summary = merged.paths.get("/dup").unwrap().get.as_ref().unwrap().summary.clone();
+        assert_eq!(summary, Some("second".to_owned()));
+    }
+}
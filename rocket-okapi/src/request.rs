@@ -0,0 +1,90 @@
+//! Contains the trait [`OpenApiFromRequest`], which mirrors `rocket::request::FromRequest` and
+//! lets a request guard describe itself to the generated OpenAPI spec.
+//!
+//! This module only defines the extension points: nothing in this crate calls
+//! `OpenApiFromRequest::from_request_input` yet. Wiring it up so that `#[openapi]` detects a
+//! handler's request guards automatically requires a matching change in the out-of-tree
+//! `revolt_rocket_okapi_codegen` proc-macro crate.
+//!
+//! Until that lands, a handler built with `openapi_get_routes_spec!` can still fold a guard's
+//! `RequestHeaderInput` into its `Operation` manually, by calling
+//! [`crate::gen::Generator::add_request_header_input`] directly:
+//! ```rust,ignore
+//! use rocket::http::Status;
+//! use rocket::request::{self, FromRequest, Outcome, Request};
+//! use revolt_okapi::openapi3::{SecurityRequirement, SecurityScheme, SecuritySchemeData};
+//! use revolt_rocket_okapi::gen::Generator;
+//! use revolt_rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+//!
+//! struct ApiKey(String);
+//!
+//! #[rocket::async_trait]
+//! impl<'r> FromRequest<'r> for ApiKey {
+//!     type Error = ();
+//!
+//!     async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, ()> {
+//!         match request.headers().get_one("x-api-key") {
+//!             Some(key) => Outcome::Success(ApiKey(key.to_owned())),
+//!             None => Outcome::Failure((Status::Unauthorized, ())),
+//!         }
+//!     }
+//! }
+//!
+//! impl<'r> OpenApiFromRequest<'r> for ApiKey {
+//!     fn from_request_input(
+//!         _gen: &mut Generator,
+//!         _name: String,
+//!         _required: bool,
+//!     ) -> revolt_rocket_okapi::Result<RequestHeaderInput> {
+//!         let scheme = SecurityScheme {
+//!             description: Some("Requires an API key in the `x-api-key` header.".to_owned()),
+//!             data: SecuritySchemeData::ApiKey {
+//!                 name: "x-api-key".to_owned(),
+//!                 location: "header".to_owned(),
+//!             },
+//!             extensions: Default::default(),
+//!         };
+//!         let mut requirement = SecurityRequirement::new();
+//!         requirement.insert("ApiKey".to_owned(), Vec::new());
+//!         Ok(RequestHeaderInput::Security("ApiKey".to_owned(), scheme, requirement))
+//!     }
+//! }
+//!
+//! // In the handler that produces `(Vec<Route>, OpenApi)`, after generating `operation`:
+//! let input = ApiKey::from_request_input(&mut gen, "ApiKey".to_owned(), true)?;
+//! gen.add_request_header_input(&mut operation, input)?;
+//! ```
+
+use crate::gen::Generator;
+use crate::Result;
+use revolt_okapi::openapi3::{SecurityRequirement, SecurityScheme};
+use rocket::request::FromRequest;
+
+/// Describes what, if anything, a request guard contributes to the documentation of the
+/// operations that use it.
+pub enum RequestHeaderInput {
+    /// This request guard requires no documentation.
+    None,
+    /// The request guard authenticates the request. Carries the name the scheme should be
+    /// registered under, the [`SecurityScheme`] itself (e.g. apiKey-in-header, http bearer, or an
+    /// oauth2 flow), and the [`SecurityRequirement`] to attach to every operation that uses this
+    /// guard.
+    ///
+    /// The [`Generator`] deduplicates schemes by name into `components.security_schemes`, so
+    /// multiple endpoints guarded by the same request guard only register the scheme once.
+    Security(String, SecurityScheme, SecurityRequirement),
+}
+
+/// A trait that allows a `rocket::request::FromRequest` implementation to also describe itself in
+/// the generated OpenAPI documentation.
+///
+/// Implement this alongside `FromRequest` for any request guard used by an `#[openapi]` endpoint
+/// that should show up in the spec, e.g. to render a documented header parameter or a lock icon
+/// for an authenticated endpoint in Swagger/RapiDoc.
+pub trait OpenApiFromRequest<'r>: FromRequest<'r> {
+    /// Describes how this request guard should be represented for the given generator.
+    ///
+    /// `name` is the name of the request guard, and `required` is `false` when the guard is
+    /// wrapped in `Option` (and therefore optional) at the call site.
+    fn from_request_input(gen: &mut Generator, name: String, required: bool) -> Result<RequestHeaderInput>;
+}
@@ -0,0 +1,73 @@
+//! Contains the [`OpenApiSettings`] struct, which can be used to customize the behavior of a
+//! [`Generator`](crate::gen::Generator).
+
+/// A URL and a title, used for the "Select a spec" dropdown in the Swagger/RapiDoc UI.
+#[derive(Debug, Clone)]
+pub struct UrlObject {
+    /// The title shown in the dropdown.
+    pub title: String,
+    /// The URL the spec can be fetched from.
+    pub url: String,
+}
+
+impl UrlObject {
+    /// Create a new `UrlObject`.
+    pub fn new(title: impl Into<String>, url: impl Into<String>) -> Self {
+        UrlObject {
+            title: title.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// Settings that control how a [`Generator`](crate::gen::Generator) produces the OpenAPI spec for
+/// a set of routes.
+#[derive(Debug, Clone)]
+pub struct OpenApiSettings {
+    /// The path the `openapi.json` route is mounted at, relative to the base path it is mounted
+    /// under. Defaults to `"/openapi.json"`.
+    pub json_path: String,
+    /// How `mount_endpoints_and_merged_docs!` should handle a colliding `paths` key when merging
+    /// the per-module specs.
+    ///
+    /// This intentionally does *not* apply to `components.schemas` collisions: a schema, unlike a
+    /// path, can be renamed without changing what callers observe, so two distinct schemas
+    /// sharing a name are always resolved by renaming rather than by `Error`/`KeepFirst`/
+    /// `KeepLast` (see [`crate::merge::merge_with_strategy`]).
+    pub on_conflict: OnConflict,
+}
+
+impl OpenApiSettings {
+    /// Create a new `OpenApiSettings` with the default `json_path` and `on_conflict` strategy.
+    pub fn new() -> Self {
+        OpenApiSettings {
+            json_path: "/openapi.json".to_owned(),
+            on_conflict: OnConflict::default(),
+        }
+    }
+}
+
+impl Default for OpenApiSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How to resolve a colliding `paths` key when merging multiple modules' specs together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Fail the merge with a structured [`crate::OpenApiError`] naming the colliding path.
+    /// This is the default, since a path collision usually means two modules were mounted at
+    /// overlapping base paths by mistake.
+    Error,
+    /// Keep the first module's definition of the path, discarding later ones.
+    KeepFirst,
+    /// Keep the last module's definition of the path, discarding earlier ones.
+    KeepLast,
+}
+
+impl Default for OnConflict {
+    fn default() -> Self {
+        OnConflict::Error
+    }
+}
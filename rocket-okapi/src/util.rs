@@ -0,0 +1,41 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Serialize an `OpenApi` spec to disk as pretty-printed JSON.
+///
+/// This is useful for dumping the merged spec at startup, or from a `build.rs` script, so it can
+/// be diffed in CI to catch accidental changes to the public API surface.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the file can not be created or written to.
+pub fn write_openapi_spec(
+    spec: &revolt_okapi::openapi3::OpenApi,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, spec)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revolt_okapi::openapi3::OpenApi;
+
+    #[test]
+    fn writes_a_spec_that_reads_back_identically() {
+        let mut spec = OpenApi::default();
+        spec.openapi = "3.0.0".to_owned();
+
+        let path = std::env::temp_dir().join("rocket_okapi_write_openapi_spec_test.json");
+        write_openapi_spec(&spec, &path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let read_back: OpenApi = serde_json::from_str(&contents).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_back, spec);
+    }
+}